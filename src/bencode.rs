@@ -0,0 +1,143 @@
+//! A minimal bencode decoder, just enough to parse tracker scrape responses.
+use std::collections::BTreeMap;
+
+/// A decoded bencode value.
+#[derive(Debug)]
+pub enum Value {
+    Int(i64),
+    Bytes(Vec<u8>),
+    List(Vec<Self>),
+    Dict(BTreeMap<Vec<u8>, Self>),
+}
+
+impl Value {
+    pub const fn as_dict(&self) -> Option<&BTreeMap<Vec<u8>, Self>> {
+        match self {
+            Self::Dict(dict) => Some(dict),
+            _ => None,
+        }
+    }
+
+    pub const fn as_int(&self) -> Option<i64> {
+        match self {
+            Self::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a single bencoded value from the start of `data`.
+pub fn parse(data: &[u8]) -> Option<Value> {
+    parse_value(data).map(|(value, _)| value)
+}
+
+fn parse_value(data: &[u8]) -> Option<(Value, &[u8])> {
+    match *data.first()? {
+        b'i' => parse_int(data),
+        b'l' => parse_list(data),
+        b'd' => parse_dict(data),
+        b'0'..=b'9' => parse_bytes(data),
+        _ => None,
+    }
+}
+
+fn parse_int(data: &[u8]) -> Option<(Value, &[u8])> {
+    let rest = data.strip_prefix(b"i")?;
+    let end = rest.iter().position(|&b| b == b'e')?;
+    let n: i64 = std::str::from_utf8(&rest[..end]).ok()?.parse().ok()?;
+    Some((Value::Int(n), &rest[end + 1..]))
+}
+
+fn parse_bytes(data: &[u8]) -> Option<(Value, &[u8])> {
+    let colon = data.iter().position(|&b| b == b':')?;
+    let len: usize = std::str::from_utf8(&data[..colon]).ok()?.parse().ok()?;
+    let rest = &data[colon + 1..];
+    if rest.len() < len {
+        return None;
+    }
+    Some((Value::Bytes(rest[..len].to_vec()), &rest[len..]))
+}
+
+fn parse_list(data: &[u8]) -> Option<(Value, &[u8])> {
+    let mut rest = data.strip_prefix(b"l")?;
+    let mut items = Vec::new();
+    while *rest.first()? != b'e' {
+        let (value, new_rest) = parse_value(rest)?;
+        items.push(value);
+        rest = new_rest;
+    }
+    Some((Value::List(items), &rest[1..]))
+}
+
+fn parse_dict(data: &[u8]) -> Option<(Value, &[u8])> {
+    let mut rest = data.strip_prefix(b"d")?;
+    let mut entries = BTreeMap::new();
+    while *rest.first()? != b'e' {
+        let (key, new_rest) = parse_bytes(rest)?;
+        let Value::Bytes(key) = key else {
+            unreachable!("parse_bytes always returns Value::Bytes")
+        };
+        let (value, new_rest) = parse_value(new_rest)?;
+        entries.insert(key, value);
+        rest = new_rest;
+    }
+    Some((Value::Dict(entries), &rest[1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Value};
+
+    #[test]
+    fn parses_int() {
+        let value = parse(b"i42e").unwrap();
+        assert_eq!(value.as_int(), Some(42));
+    }
+
+    #[test]
+    fn parses_negative_int() {
+        let value = parse(b"i-42e").unwrap();
+        assert_eq!(value.as_int(), Some(-42));
+    }
+
+    #[test]
+    fn parses_bytes() {
+        let value = parse(b"4:spam").unwrap();
+        assert!(matches!(value, Value::Bytes(b) if b == b"spam"));
+    }
+
+    #[test]
+    fn parses_list() {
+        let value = parse(b"l4:spam4:eggse").unwrap();
+        let Value::List(items) = value else {
+            panic!("expected a list");
+        };
+        assert_eq!(items.len(), 2);
+        assert!(matches!(&items[0], Value::Bytes(b) if b == b"spam"));
+        assert!(matches!(&items[1], Value::Bytes(b) if b == b"eggs"));
+    }
+
+    #[test]
+    fn parses_dict() {
+        // A minimal tracker scrape response shape: {"files": {<hash>: {"complete": 1}}}.
+        let value = parse(b"d5:filesd4:hashd8:completei1eeee").unwrap();
+        let files = value.as_dict().unwrap().get(b"files".as_slice()).unwrap();
+        let stats = files.as_dict().unwrap().get(b"hash".as_slice()).unwrap();
+        assert_eq!(
+            stats
+                .as_dict()
+                .unwrap()
+                .get(b"complete".as_slice())
+                .unwrap()
+                .as_int(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn truncated_input_is_rejected() {
+        assert!(parse(b"4:sp").is_none());
+        assert!(parse(b"i42").is_none());
+        assert!(parse(b"d4:spam").is_none());
+    }
+}
@@ -0,0 +1,123 @@
+//! On-disk response caching with a TTL.
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// Caches raw JSON response bodies on disk, keyed by their fully-resolved URL.
+#[derive(Debug)]
+pub struct Cache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    fetched_at: i64,
+    body: String,
+}
+
+impl Cache {
+    pub(crate) const fn new(dir: PathBuf, ttl: Duration) -> Self {
+        Self { dir, ttl }
+    }
+
+    fn path_for(&self, url: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        self.dir.join(format!("{:016x}.json", hasher.finish()))
+    }
+
+    /// Return the cached body for `url`, if a fresh entry exists.
+    pub(crate) fn get(&self, url: &str) -> Result<Option<String>> {
+        let path = self.path_for(url);
+        let raw = match std::fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let entry: CacheEntry = serde_json::from_str(&raw)?;
+        let age = Utc::now().timestamp() - entry.fetched_at;
+        if age < 0 || age.cast_unsigned() > self.ttl.as_secs() {
+            return Ok(None);
+        }
+        Ok(Some(entry.body))
+    }
+
+    /// Write `body` to the cache entry for `url`.
+    pub(crate) fn put(&self, url: &str, body: &str) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let entry = CacheEntry {
+            fetched_at: Utc::now().timestamp(),
+            body: body.to_string(),
+        };
+        std::fs::write(self.path_for(url), serde_json::to_string(&entry)?)?;
+        Ok(())
+    }
+
+    /// Remove every cached entry.
+    pub(crate) fn clear(&self) -> Result<()> {
+        match std::fs::remove_dir_all(&self.dir) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cache;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    fn temp_dir() -> std::path::PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("tpb-cache-test-{}-{n}", std::process::id()))
+    }
+
+    #[test]
+    fn put_then_get_returns_cached_body() {
+        let cache = Cache::new(temp_dir(), Duration::from_mins(1));
+        cache.put("http://example.com", "body").unwrap();
+        assert_eq!(
+            cache.get("http://example.com").unwrap(),
+            Some("body".to_string())
+        );
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn get_returns_none_for_missing_entry() {
+        let cache = Cache::new(temp_dir(), Duration::from_mins(1));
+        assert_eq!(cache.get("http://example.com").unwrap(), None);
+    }
+
+    #[test]
+    fn expired_entry_is_not_returned() {
+        let cache = Cache::new(temp_dir(), Duration::from_mins(1));
+        std::fs::create_dir_all(&cache.dir).unwrap();
+        // Write a stale entry directly rather than relying on real time passing.
+        let stale_timestamp = chrono::Utc::now().timestamp() - 3600;
+        let path = cache.path_for("http://example.com");
+        let body = serde_json::json!({"fetched_at": stale_timestamp, "body": "body"});
+        std::fs::write(&path, body.to_string()).unwrap();
+
+        assert_eq!(cache.get("http://example.com").unwrap(), None);
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn clear_removes_all_entries() {
+        let cache = Cache::new(temp_dir(), Duration::from_mins(1));
+        cache.put("http://example.com", "body").unwrap();
+        cache.clear().unwrap();
+        assert_eq!(cache.get("http://example.com").unwrap(), None);
+    }
+}
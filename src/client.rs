@@ -0,0 +1,397 @@
+//! The configurable [`Tpb`] client.
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
+
+use crate::cache::Cache;
+use crate::{Category, PartialTorrent, Result, Torrent, TorrentFile, API};
+
+/// A configurable client for The Pirate Bay's JSON API.
+///
+/// Construct one with [`Tpb::builder`] to override the HTTP client, API base URL,
+/// user agent, timeout, rate limit, or response cache, or use [`Tpb::new`] to
+/// get a client with sensible defaults. The free functions at the crate root
+/// (e.g. [`crate::search`]) are thin wrappers around a lazily-built default
+/// `Tpb` for callers who don't need any of this.
+#[derive(Debug, Clone)]
+pub struct Tpb {
+    pub(crate) client: reqwest::Client,
+    pub(crate) api: String,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    cache: Option<Arc<Cache>>,
+}
+
+/// Serialises requests so consecutive calls are spaced at least `min_interval` apart.
+#[derive(Debug)]
+struct RateLimiter {
+    min_interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Sleep until at least `min_interval` has passed since the last call to this method.
+    async fn wait(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(last_request) = *last_request {
+            let elapsed = last_request.elapsed();
+            if let Some(remaining) = self.min_interval.checked_sub(elapsed) {
+                tokio::time::sleep(remaining).await;
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+impl Default for Tpb {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl Tpb {
+    /// Wait out the configured rate limit, if any, before sending a request.
+    async fn throttle(&self) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.wait().await;
+        }
+    }
+
+    /// Send `request`, transparently serving it from or storing it in the
+    /// response cache if one is configured.
+    async fn fetch<T: DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T> {
+        let request = request.build()?;
+        let url = request.url().as_str().to_string();
+
+        if let Some(cache) = &self.cache {
+            if let Some(body) = cache.get(&url)? {
+                return Ok(serde_json::from_str(&body)?);
+            }
+        }
+
+        self.throttle().await;
+        let response = self.client.execute(request).await?;
+        let is_success = response.status().is_success();
+        let body = response.text().await?;
+        if is_success {
+            if let Some(cache) = &self.cache {
+                cache.put(&url, &body)?;
+            }
+        }
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// Create a client with default settings (the public apibay.org API, a
+    /// plain [`reqwest::Client`], and no timeout, rate limit, or cache).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start building a customised client.
+    #[must_use]
+    pub fn builder() -> TpbBuilder {
+        TpbBuilder::default()
+    }
+
+    /// Returns a lightweight clone of this client with caching disabled, for
+    /// bypassing the cache on a one-off call, e.g. `client.bypass_cache().torrent(id)`.
+    #[must_use]
+    pub fn bypass_cache(&self) -> Self {
+        Self {
+            cache: None,
+            ..self.clone()
+        }
+    }
+
+    /// Remove every entry from the response cache.
+    ///
+    /// Does nothing if no cache is configured.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the cache directory could not be removed.
+    pub fn clear_cache(&self) -> Result<()> {
+        self.cache.as_ref().map_or(Ok(()), |cache| cache.clear())
+    }
+
+    /// Search for torrents by name match and optionally category.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the request fails or the response is invalid.
+    pub async fn search(
+        &self,
+        query: &str,
+        category: Option<Category>,
+    ) -> Result<Vec<PartialTorrent>> {
+        let cat = category.map(|cat| cat.0.to_string()).unwrap_or_default();
+        self.fetch(
+            self.client
+                .get(format!("{}/q.php", self.api))
+                .query(&[("q", query), ("cat", &cat)]),
+        )
+        .await
+    }
+
+    /// Get the top 100 torrents by category.
+    ///
+    /// If `last_48h` is true, only torrents uploaded in the last 48 hours are returned.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the request fails or the response is invalid.
+    pub async fn top100(&self, category: Category, last_48h: bool) -> Result<Vec<PartialTorrent>> {
+        let specifier = if last_48h { "_48h" } else { "" };
+        self.fetch(self.client.get(format!(
+            "{api}/precompiled/data_top100{spec}_{cat}.json",
+            api = self.api,
+            spec = specifier,
+            cat = category.0,
+        )))
+        .await
+    }
+
+    /// Search for torrents associated with an `IMDb` title.
+    ///
+    /// Accepts a bare id (`tt0133093` or `0133093`) or a full `IMDb` URL.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the request fails or the response is invalid.
+    pub async fn search_imdb(&self, imdb_id: &str) -> Result<Vec<PartialTorrent>> {
+        let id = normalize_imdb_id(imdb_id);
+        self.fetch(
+            self.client
+                .get(format!("{}/q.php", self.api))
+                .query(&[("q", id.as_str())]),
+        )
+        .await
+    }
+
+    /// Get full metadata on a torrent by ID.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the request fails or the response is invalid.
+    pub async fn torrent(&self, id: u64) -> Result<Torrent> {
+        self.fetch(
+            self.client
+                .get(format!("{}/t.php", self.api))
+                .query(&[("id", id.to_string())]),
+        )
+        .await
+    }
+
+    /// Get a list of file metadata for a torrent by ID.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the request fails or the response is invalid.
+    pub async fn torrent_files(&self, id: u64) -> Result<Vec<TorrentFile>> {
+        self.fetch(
+            self.client
+                .get(format!("{}/f.php", self.api))
+                .query(&[("id", id.to_string())]),
+        )
+        .await
+    }
+}
+
+/// Builder for [`Tpb`], letting callers inject their own [`reqwest::Client`],
+/// point at an alternate JSON-API host, set a user agent and timeout, enable
+/// rate limiting, or enable on-disk response caching.
+#[derive(Debug, Default)]
+pub struct TpbBuilder {
+    client: Option<reqwest::Client>,
+    api: Option<String>,
+    user_agent: Option<String>,
+    timeout: Option<Duration>,
+    min_interval: Option<Duration>,
+    cache_dir: Option<PathBuf>,
+    cache_ttl: Option<Duration>,
+}
+
+impl TpbBuilder {
+    /// Use this [`reqwest::Client`] instead of building one from the other options.
+    ///
+    /// When set, `user_agent` and `timeout` below are ignored - configure them
+    /// on the supplied client instead.
+    #[must_use]
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Override the API base URL (defaults to `https://apibay.org`).
+    ///
+    /// Useful for targeting alternate JSON-API hosts, since most mirrors don't
+    /// speak this API.
+    #[must_use]
+    pub fn api_base(mut self, api: impl Into<String>) -> Self {
+        self.api = Some(api.into());
+        self
+    }
+
+    /// Set the `User-Agent` header sent with every request.
+    #[must_use]
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Set a timeout applied to every request.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Space out requests so consecutive calls are at least `min_interval` apart.
+    ///
+    /// apibay enforces a per-IP request cap, similar to other torrent APIs that
+    /// document a hard limit of one request per couple of seconds. Disabled by
+    /// default, so existing callers are unaffected.
+    #[must_use]
+    pub const fn min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = Some(min_interval);
+        self
+    }
+
+    /// Cache responses on disk under `dir` for `ttl`, keyed by the
+    /// fully-resolved request URL.
+    ///
+    /// A fresh cache hit is served without making an HTTP request. This also
+    /// enables offline replay, and cuts down on traffic when rate limiting is
+    /// in effect. Disabled by default.
+    #[must_use]
+    pub fn cache(mut self, dir: impl Into<PathBuf>, ttl: Duration) -> Self {
+        self.cache_dir = Some(dir.into());
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Build the configured [`Tpb`] client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no [`client`](Self::client) was supplied and a client could
+    /// not be built from the given `user_agent`/`timeout` options.
+    #[must_use]
+    pub fn build(self) -> Tpb {
+        let client = self.client.unwrap_or_else(|| {
+            let mut builder = reqwest::Client::builder();
+            if let Some(user_agent) = self.user_agent {
+                builder = builder.user_agent(user_agent);
+            }
+            if let Some(timeout) = self.timeout {
+                builder = builder.timeout(timeout);
+            }
+            // The `rustls-tls-*` features select rustls over the default
+            // native-tls (OpenSSL) backend at the reqwest level; this just
+            // asks the client to use whichever backend was compiled in.
+            #[cfg(any(
+                feature = "rustls-tls-webpki-roots",
+                feature = "rustls-tls-native-roots"
+            ))]
+            {
+                builder = builder.use_rustls_tls();
+            }
+            builder.build().expect("failed to build reqwest client")
+        });
+        Tpb {
+            client,
+            api: self.api.unwrap_or_else(|| API.to_string()),
+            rate_limiter: self
+                .min_interval
+                .map(|interval| Arc::new(RateLimiter::new(interval))),
+            cache: self
+                .cache_dir
+                .map(|dir| Arc::new(Cache::new(dir, self.cache_ttl.unwrap_or_default()))),
+        }
+    }
+}
+
+/// Normalize `tt0133093`, `0133093`, or a full `IMDb` URL (including one
+/// pointing at a sub-page like `/fullcredits` or `/reviews`) into the
+/// `tt`-prefixed form apibay expects as its `q` parameter.
+fn normalize_imdb_id(imdb_id: &str) -> String {
+    // Drop a query string or fragment left over from a pasted URL, e.g.
+    // `tt0133093/?ref_=...`.
+    let without_suffix = imdb_id.split(['?', '#']).next().unwrap_or(imdb_id);
+    // IMDb URLs always put the title id right after `/title/`, regardless of
+    // how many more path segments follow it (`/fullcredits/`, `/reviews/`, ...),
+    // so scan for the segment that looks like one instead of assuming it's last.
+    let id = without_suffix
+        .split('/')
+        .find(|segment| is_title_id(segment))
+        .unwrap_or(without_suffix);
+    if id.starts_with("tt") {
+        id.to_string()
+    } else {
+        format!("tt{id}")
+    }
+}
+
+/// Whether `segment` looks like an `IMDb` title id: `tt` followed by one or more digits.
+fn is_title_id(segment: &str) -> bool {
+    segment
+        .strip_prefix("tt")
+        .is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_imdb_id;
+
+    #[test]
+    fn bare_id() {
+        assert_eq!(normalize_imdb_id("tt0133093"), "tt0133093");
+    }
+
+    #[test]
+    fn id_without_tt_prefix() {
+        assert_eq!(normalize_imdb_id("0133093"), "tt0133093");
+    }
+
+    #[test]
+    fn full_url() {
+        assert_eq!(
+            normalize_imdb_id("https://www.imdb.com/title/tt0133093/"),
+            "tt0133093"
+        );
+    }
+
+    #[test]
+    fn full_url_with_query_string() {
+        assert_eq!(
+            normalize_imdb_id("https://www.imdb.com/title/tt0133093/?ref_=nv_sr_srsg_0"),
+            "tt0133093"
+        );
+    }
+
+    #[test]
+    fn sub_page_url() {
+        assert_eq!(
+            normalize_imdb_id("https://www.imdb.com/title/tt0133093/fullcredits/"),
+            "tt0133093"
+        );
+    }
+
+    #[test]
+    fn sub_page_url_with_query_string() {
+        assert_eq!(
+            normalize_imdb_id("https://www.imdb.com/title/tt0133093/reviews/?ref_=tt_ov_rt"),
+            "tt0133093"
+        );
+    }
+}
@@ -0,0 +1,57 @@
+//! The crate's error type.
+use std::fmt;
+
+/// Errors that can occur while using this crate.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The underlying HTTP request failed, or the response body could not be
+    /// read.
+    Request(reqwest::Error),
+    /// Reading from or writing to the on-disk response cache failed.
+    Cache(std::io::Error),
+    /// A response body (live or cached) could not be deserialized.
+    Decode(serde_json::Error),
+    /// A tracker scrape response was malformed, or no tracker responded.
+    ScrapeFailed(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Request(err) => write!(f, "request failed: {err}"),
+            Self::Cache(err) => write!(f, "cache access failed: {err}"),
+            Self::Decode(err) => write!(f, "failed to decode response: {err}"),
+            Self::ScrapeFailed(reason) => write!(f, "tracker scrape failed: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Request(err) => Some(err),
+            Self::Cache(err) => Some(err),
+            Self::Decode(err) => Some(err),
+            Self::ScrapeFailed(_) => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Request(err)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Cache(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Decode(err)
+    }
+}
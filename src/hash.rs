@@ -0,0 +1,162 @@
+//! A validated torrent info hash.
+use std::fmt::{self, Write};
+
+use serde::de::{self, Deserialize, Deserializer};
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// A torrent's 20-byte SHA-1 info hash.
+///
+/// apibay returns this as 40-character hex, but torrent info hashes are more
+/// commonly seen as 32-character base32 (as in magnet URIs from other
+/// sources), so both forms deserialize into this type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InfoHash([u8; 20]);
+
+impl InfoHash {
+    /// The raw 20 bytes of the hash.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    /// The hash as 40 lowercase hex characters.
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        self.0
+            .iter()
+            .fold(String::with_capacity(40), |mut out, byte| {
+                let _ = write!(out, "{byte:02x}");
+                out
+            })
+    }
+
+    /// The hash as 32 uppercase base32 characters.
+    #[must_use]
+    pub fn to_base32(&self) -> String {
+        // 20 bytes (160 bits) split into 32 groups of 5 bits, with no padding needed.
+        let mut out = String::with_capacity(32);
+        let mut bit_buffer: u32 = 0;
+        let mut bits_in_buffer = 0u32;
+        for &byte in &self.0 {
+            bit_buffer = (bit_buffer << 8) | u32::from(byte);
+            bits_in_buffer += 8;
+            while bits_in_buffer >= 5 {
+                bits_in_buffer -= 5;
+                let index = (bit_buffer >> bits_in_buffer) & 0x1f;
+                out.push(BASE32_ALPHABET[index as usize] as char);
+            }
+        }
+        out
+    }
+
+    fn from_hex(hex: &str) -> Option<Self> {
+        if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let mut bytes = [0u8; 20];
+        for (byte, chunk) in bytes.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+            let hex = std::str::from_utf8(chunk).expect("already validated as ASCII hex");
+            *byte = u8::from_str_radix(hex, 16).expect("already validated as ASCII hex");
+        }
+        Some(Self(bytes))
+    }
+
+    fn from_base32(base32: &str) -> Option<Self> {
+        if base32.len() != 32 {
+            return None;
+        }
+        let mut bytes = [0u8; 20];
+        let mut bit_buffer: u64 = 0;
+        let mut bits_in_buffer = 0u32;
+        let mut byte_index = 0;
+        for c in base32.to_ascii_uppercase().bytes() {
+            let value = BASE32_ALPHABET.iter().position(|&b| b == c)? as u64;
+            bit_buffer = (bit_buffer << 5) | value;
+            bits_in_buffer += 5;
+            if bits_in_buffer >= 8 {
+                bits_in_buffer -= 8;
+                bytes[byte_index] = ((bit_buffer >> bits_in_buffer) & 0xff) as u8;
+                byte_index += 1;
+            }
+        }
+        Some(Self(bytes))
+    }
+}
+
+impl fmt::Display for InfoHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for InfoHash {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Self::from_hex(&value)
+            .or_else(|| Self::from_base32(&value))
+            .ok_or_else(|| {
+                de::Error::custom("expected a 40-character hex or 32-character base32 info hash")
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InfoHash;
+
+    const HEX: &str = "c12fe1c06bba254a9dc9f519b335aa7c1367a88a";
+    const BASE32: &str = "YEX6DQDLXISUVHOJ6UM3GNNKPQJWPKEK";
+
+    #[test]
+    fn hex_round_trip() {
+        let hash = InfoHash::from_hex(HEX).unwrap();
+        assert_eq!(hash.to_hex(), HEX);
+    }
+
+    #[test]
+    fn base32_round_trip() {
+        let hash = InfoHash::from_base32(BASE32).unwrap();
+        assert_eq!(hash.to_base32(), BASE32);
+    }
+
+    #[test]
+    fn hex_and_base32_agree() {
+        let from_hex = InfoHash::from_hex(HEX).unwrap();
+        let from_base32 = InfoHash::from_base32(BASE32).unwrap();
+        assert_eq!(from_hex, from_base32);
+        assert_eq!(from_hex.to_base32(), BASE32);
+        assert_eq!(from_base32.to_hex(), HEX);
+    }
+
+    #[test]
+    fn from_hex_rejects_wrong_length() {
+        assert!(InfoHash::from_hex("abc").is_none());
+    }
+
+    #[test]
+    fn from_hex_rejects_non_hex_chars() {
+        assert!(InfoHash::from_hex(&"g".repeat(40)).is_none());
+    }
+
+    #[test]
+    fn from_base32_rejects_wrong_length() {
+        assert!(InfoHash::from_base32("abc").is_none());
+    }
+
+    #[test]
+    fn from_base32_rejects_invalid_chars() {
+        // '0', '1', '8', '9' are not in the RFC4648 base32 alphabet.
+        assert!(InfoHash::from_base32(&"0".repeat(32)).is_none());
+    }
+
+    #[test]
+    fn deserializes_from_hex_or_base32() {
+        let from_hex: InfoHash = serde_json::from_value(serde_json::json!(HEX)).unwrap();
+        let from_base32: InfoHash = serde_json::from_value(serde_json::json!(BASE32)).unwrap();
+        assert_eq!(from_hex, from_base32);
+    }
+}
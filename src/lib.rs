@@ -6,6 +6,13 @@
 //!
 //! This client uses the JSON API and as such currently won't work with most mirrors.
 //!
+//! # TLS backend
+//!
+//! By default this crate pulls in reqwest's `default-tls` (native-tls/OpenSSL)
+//! backend. For static/musl builds or cross-compilation, switch to rustls with
+//! `default-features = false` plus either the `rustls-tls-webpki-roots` or
+//! `rustls-tls-native-roots` feature.
+//!
 //! # Example
 //!
 //! ```
@@ -33,102 +40,84 @@
 //! # }
 //! ```
 #![warn(clippy::all, clippy::pedantic, clippy::nursery)]
+mod bencode;
+mod cache;
+mod client;
 mod deser;
+mod error;
+mod hash;
+mod query;
+mod scrape;
 mod scraped;
 mod types;
 
+pub use client::{Tpb, TpbBuilder};
+pub use error::Error;
+pub use hash::InfoHash;
+pub use query::{Order, SearchQuery, SortBy};
+pub use scrape::ScrapeStats;
 pub use scraped::{CATEGORIES, TRACKERS};
 pub use types::*;
 
+use std::sync::OnceLock;
+
 const API: &str = "https://apibay.org";
-type Result<T> = std::result::Result<T, reqwest::Error>;
+pub(crate) type Result<T> = std::result::Result<T, Error>;
 
-thread_local! {
-    static CLIENT: reqwest::Client = reqwest::Client::new();
+/// The default [`Tpb`] client backing the free functions below.
+fn default_client() -> &'static Tpb {
+    static DEFAULT: OnceLock<Tpb> = OnceLock::new();
+    DEFAULT.get_or_init(Tpb::new)
 }
 
 /// Search for torrents by name match and optionally category.
 ///
+/// This is a thin wrapper around [`Tpb::search`] on a default client; use
+/// [`Tpb::builder`] if you need to customise the client or target.
+///
 /// # Errors
 ///
 /// This function returns an error if the request fails or the response is invalid.
 pub async fn search(query: &str, category: Option<Category>) -> Result<Vec<PartialTorrent>> {
-    let cat = category.map(|cat| cat.0.to_string()).unwrap_or_default();
-    let torrents = CLIENT
-        .with(|client| {
-            client
-                .get(format!("{API}/q.php"))
-                .query(&[("q", query), ("cat", &cat)])
-                .send()
-        })
-        .await?
-        .json()
-        .await?;
-    Ok(torrents)
+    default_client().search(query, category).await
 }
 
 /// Get the top 100 torrents by category.
 ///
 /// If `last_48h` is true, only torrents uploaded in the last 48 hours are returned.
 ///
+/// This is a thin wrapper around [`Tpb::top100`] on a default client; use
+/// [`Tpb::builder`] if you need to customise the client or target.
+///
 /// # Errors
 ///
 /// This function returns an error if the request fails or the response is invalid.
 pub async fn top100(category: Category, last_48h: bool) -> Result<Vec<PartialTorrent>> {
-    let specifier = if last_48h { "_48h" } else { "" };
-    let torrents = CLIENT
-        .with(|client| {
-            client
-                .get(format!(
-                    "{API}/precompiled/data_top100{spec}_{cat}.json",
-                    API = API,
-                    spec = specifier,
-                    cat = category.0,
-                ))
-                .send()
-        })
-        .await?
-        .json()
-        .await?;
-    Ok(torrents)
+    default_client().top100(category, last_48h).await
 }
 
 /// Get full metadata on a torrent by ID.
 ///
+/// This is a thin wrapper around [`Tpb::torrent`] on a default client; use
+/// [`Tpb::builder`] if you need to customise the client or target.
+///
 /// # Errors
 ///
 /// This function returns an error if the request fails or the response is invalid.
 pub async fn torrent(id: u64) -> Result<Torrent> {
-    let torrent = CLIENT
-        .with(|client| {
-            client
-                .get(format!("{API}/t.php"))
-                .query(&[("id", id.to_string())])
-                .send()
-        })
-        .await?
-        .json()
-        .await?;
-    Ok(torrent)
+    default_client().torrent(id).await
 }
 
 /// Get a list of file metadata for a torrent by ID.
 ///
+/// This is a thin wrapper around [`Tpb::torrent_files`] on a default client; use
+/// [`Tpb::builder`] if you need to customise the client or target.
+///
 /// # Errors
 ///
 /// This function returns an error if the request fails or the response is invalid.
 pub async fn torrent_files(id: u64) -> Result<Vec<TorrentFile>> {
-    let files = CLIENT
-        .with(|client| {
-            client
-                .get(format!("{API}/f.php"))
-                .query(&[("id", id.to_string())])
-                .send()
-        })
-        .await?
-        .json()
-        .await?;
-    Ok(files)
+    default_client().torrent_files(id).await
 }
 
 impl PartialTorrent {
@@ -136,9 +125,11 @@ impl PartialTorrent {
     #[must_use]
     pub fn magnet(&self) -> String {
         // We encode the hash manually because the URL builder escapes the colons.
-        format!("magnet:?xt=urn:btih:{}", self.info_hash)
+        // This can never fail to parse: `InfoHash::to_hex` only ever produces
+        // hex digits, which are always valid here.
+        format!("magnet:?xt=urn:btih:{}", self.info_hash.to_hex())
             .parse::<reqwest::Url>()
-            .expect("magnet link failed to parse - invalid info hash?")
+            .expect("info_hash is valid hex, so this is always parseable")
             .query_pairs_mut()
             // TPB has slightly different escaping rules here but it doesn't seem to be an issue.
             .append_pair("dn", &self.name)
@@ -0,0 +1,221 @@
+//! The [`SearchQuery`] builder, for sorting, pagination, and result filtering.
+use crate::{Category, PartialTorrent, Result, Tpb};
+
+/// Ascending or descending sort order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+/// A field to sort search results by, paired with a direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    Seeders(Order),
+    Leechers(Order),
+    Size(Order),
+    Added(Order),
+    Name(Order),
+}
+
+/// A builder for a richer torrent search: sorting, pagination, and a
+/// minimum-seeder filter on top of the plain name/category match.
+///
+/// apibay's `q.php` endpoint returns an unsorted full list of matches, so
+/// sorting, filtering, and pagination are all applied client-side over the
+/// deserialized results. Build one with [`Tpb::query`].
+#[derive(Debug)]
+#[must_use = "a SearchQuery does nothing until you call `.send()`"]
+pub struct SearchQuery<'a> {
+    tpb: &'a Tpb,
+    query: String,
+    category: Option<Category>,
+    sort: Option<SortBy>,
+    min_seeders: Option<u64>,
+    page: Option<(usize, usize)>,
+}
+
+impl<'a> SearchQuery<'a> {
+    pub(crate) fn new(tpb: &'a Tpb, query: &str) -> Self {
+        Self {
+            tpb,
+            query: query.to_string(),
+            category: None,
+            sort: None,
+            min_seeders: None,
+            page: None,
+        }
+    }
+
+    /// Restrict results to a single category.
+    pub const fn category(mut self, category: Category) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Sort results by the given field and direction.
+    pub const fn sort(mut self, sort: SortBy) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Drop results with fewer than `min_seeders` seeders.
+    pub const fn min_seeders(mut self, min_seeders: u64) -> Self {
+        self.min_seeders = Some(min_seeders);
+        self
+    }
+
+    /// Take `limit` results starting at `offset`, applied after sorting and filtering.
+    pub const fn page(mut self, offset: usize, limit: usize) -> Self {
+        self.page = Some((offset, limit));
+        self
+    }
+
+    /// Run the search and apply the configured sorting, filtering, and pagination.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the request fails or the response is invalid.
+    pub async fn send(self) -> Result<Vec<PartialTorrent>> {
+        let mut torrents = self.tpb.search(&self.query, self.category).await?;
+
+        if let Some(min_seeders) = self.min_seeders {
+            filter_min_seeders(&mut torrents, min_seeders);
+        }
+
+        if let Some(sort) = self.sort {
+            sort_torrents(&mut torrents, sort);
+        }
+
+        if let Some((offset, limit)) = self.page {
+            torrents = paginate(torrents, offset, limit);
+        }
+
+        Ok(torrents)
+    }
+}
+
+fn filter_min_seeders(torrents: &mut Vec<PartialTorrent>, min_seeders: u64) {
+    torrents.retain(|torrent| torrent.seeders >= min_seeders);
+}
+
+fn paginate(torrents: Vec<PartialTorrent>, offset: usize, limit: usize) -> Vec<PartialTorrent> {
+    torrents.into_iter().skip(offset).take(limit).collect()
+}
+
+fn sort_torrents(torrents: &mut [PartialTorrent], sort: SortBy) {
+    match sort {
+        SortBy::Seeders(order) => sort_by_key(torrents, order, |t| t.seeders),
+        SortBy::Leechers(order) => sort_by_key(torrents, order, |t| t.leechers),
+        SortBy::Size(order) => sort_by_key(torrents, order, |t| t.size),
+        SortBy::Added(order) => sort_by_key(torrents, order, |t| t.added),
+        SortBy::Name(order) => sort_by_key(torrents, order, |t| t.name.clone()),
+    }
+}
+
+fn sort_by_key<K: Ord>(
+    torrents: &mut [PartialTorrent],
+    order: Order,
+    key: impl Fn(&PartialTorrent) -> K,
+) {
+    torrents.sort_by(|a, b| {
+        let ordering = key(a).cmp(&key(b));
+        match order {
+            Order::Asc => ordering,
+            Order::Desc => ordering.reverse(),
+        }
+    });
+}
+
+impl Tpb {
+    /// Start building a richer search with sorting, pagination, and a
+    /// minimum-seeder filter.
+    pub fn query(&self, query: &str) -> SearchQuery<'_> {
+        SearchQuery::new(self, query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filter_min_seeders, paginate, sort_torrents, Order, SortBy};
+    use crate::PartialTorrent;
+
+    fn torrent(id: u64, name: &str, seeders: u64, size: u64) -> PartialTorrent {
+        serde_json::from_value(serde_json::json!({
+            "id": id.to_string(),
+            "name": name,
+            "info_hash": "0".repeat(40),
+            "leechers": "0",
+            "seeders": seeders.to_string(),
+            "num_files": "1",
+            "size": size.to_string(),
+            "username": "uploader",
+            "added": "1700000000",
+            "status": "member",
+            "category": "201",
+            "imdb": "",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn sort_by_seeders_ascending() {
+        let mut torrents = vec![
+            torrent(1, "a", 5, 0),
+            torrent(2, "b", 1, 0),
+            torrent(3, "c", 3, 0),
+        ];
+        sort_torrents(&mut torrents, SortBy::Seeders(Order::Asc));
+        assert_eq!(
+            torrents.iter().map(|t| t.id).collect::<Vec<_>>(),
+            vec![2, 3, 1]
+        );
+    }
+
+    #[test]
+    fn sort_by_name_descending() {
+        let mut torrents = vec![
+            torrent(1, "a", 0, 0),
+            torrent(2, "c", 0, 0),
+            torrent(3, "b", 0, 0),
+        ];
+        sort_torrents(&mut torrents, SortBy::Name(Order::Desc));
+        assert_eq!(
+            torrents.iter().map(|t| t.id).collect::<Vec<_>>(),
+            vec![2, 3, 1]
+        );
+    }
+
+    #[test]
+    fn filter_drops_torrents_below_min_seeders() {
+        let mut torrents = vec![
+            torrent(1, "a", 5, 0),
+            torrent(2, "b", 1, 0),
+            torrent(3, "c", 10, 0),
+        ];
+        filter_min_seeders(&mut torrents, 5);
+        assert_eq!(
+            torrents.iter().map(|t| t.id).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+    }
+
+    #[test]
+    fn page_takes_a_slice() {
+        let torrents = vec![
+            torrent(1, "a", 0, 0),
+            torrent(2, "b", 0, 0),
+            torrent(3, "c", 0, 0),
+            torrent(4, "d", 0, 0),
+        ];
+        let page = paginate(torrents, 1, 2);
+        assert_eq!(page.iter().map(|t| t.id).collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn page_past_the_end_is_empty() {
+        let torrents = vec![torrent(1, "a", 0, 0)];
+        let page = paginate(torrents, 5, 2);
+        assert!(page.is_empty());
+    }
+}
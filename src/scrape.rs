@@ -0,0 +1,136 @@
+//! Live seeder/leecher counts via the `BitTorrent` tracker scrape convention.
+use std::fmt::Write;
+
+use crate::bencode::{self, Value};
+use crate::{Error, PartialTorrent, Result, Tpb, TRACKERS};
+
+/// Live peer counts for a torrent, aggregated across trackers.
+///
+/// The seeder/leecher counts from `q.php`/`t.php` are stale snapshots; this is
+/// fetched fresh from the trackers via [`PartialTorrent::scrape`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ScrapeStats {
+    pub seeders: u64,
+    pub leechers: u64,
+    pub completed: u64,
+}
+
+impl PartialTorrent {
+    /// Query the trackers in [`TRACKERS`] for live seeder/leecher counts via
+    /// the tracker scrape convention, aggregating the maximum seen across
+    /// trackers. Non-HTTP(S) trackers (e.g. `udp://`) are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if every tracker failed to respond.
+    pub async fn scrape(&self, client: &Tpb) -> Result<ScrapeStats> {
+        let scrapes = futures::future::join_all(
+            TRACKERS
+                .iter()
+                .filter_map(|tracker| scrape_url(tracker, self.info_hash.as_bytes()))
+                .map(|url| scrape_one(client, url, self.info_hash.as_bytes())),
+        )
+        .await;
+
+        scrapes
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .reduce(|a, b| ScrapeStats {
+                seeders: a.seeders.max(b.seeders),
+                leechers: a.leechers.max(b.leechers),
+                completed: a.completed.max(b.completed),
+            })
+            .ok_or_else(|| Error::ScrapeFailed("no tracker responded".to_string()))
+    }
+}
+
+/// Derive a tracker's HTTP scrape URL from its announce URL, per the
+/// convention of replacing the final `announce` path segment with `scrape`.
+/// Returns `None` for non-HTTP(S) trackers or ones that don't follow the
+/// convention.
+fn scrape_url(announce: &str, hash: &[u8; 20]) -> Option<reqwest::Url> {
+    let mut url = reqwest::Url::parse(announce).ok()?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return None;
+    }
+
+    let segments: Vec<&str> = url.path_segments()?.collect();
+    let (last, rest) = segments.split_last()?;
+    if *last != "announce" {
+        return None;
+    }
+    let scrape_segments: Vec<&str> = rest.iter().copied().chain(["scrape"]).collect();
+    url.set_path(&format!("/{}", scrape_segments.join("/")));
+
+    let mut encoded_hash = String::with_capacity(hash.len() * 3);
+    for byte in hash {
+        encoded_hash.push('%');
+        let _ = write!(encoded_hash, "{byte:02X}");
+    }
+    url.set_query(Some(&format!("info_hash={encoded_hash}")));
+
+    Some(url)
+}
+
+async fn scrape_one(client: &Tpb, url: reqwest::Url, hash: &[u8; 20]) -> Result<ScrapeStats> {
+    let body = client.client.get(url).send().await?.bytes().await?;
+
+    let value = bencode::parse(&body)
+        .ok_or_else(|| Error::ScrapeFailed("malformed response".to_string()))?;
+    let files = value
+        .as_dict()
+        .and_then(|dict| dict.get(b"files".as_slice()))
+        .and_then(Value::as_dict)
+        .ok_or_else(|| Error::ScrapeFailed("response has no files entry".to_string()))?;
+    let stats = files
+        .get(hash.as_slice())
+        .and_then(Value::as_dict)
+        .ok_or_else(|| {
+            Error::ScrapeFailed("response has no stats for this info hash".to_string())
+        })?;
+
+    let field = |name: &[u8]| {
+        stats
+            .get(name)
+            .and_then(Value::as_int)
+            .unwrap_or(0)
+            .max(0)
+            .cast_unsigned()
+    };
+    Ok(ScrapeStats {
+        seeders: field(b"complete"),
+        leechers: field(b"incomplete"),
+        completed: field(b"downloaded"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scrape_url;
+
+    const HASH: [u8; 20] = [0u8; 20];
+
+    #[test]
+    fn single_segment_announce_path() {
+        // The common case for public trackers: `/announce` with nothing before it.
+        let url = scrape_url("http://tracker.example.com:1337/announce", &HASH).unwrap();
+        assert_eq!(url.path(), "/scrape");
+    }
+
+    #[test]
+    fn multi_segment_announce_path() {
+        let url = scrape_url("http://tracker.example.com/forums/announce", &HASH).unwrap();
+        assert_eq!(url.path(), "/forums/scrape");
+    }
+
+    #[test]
+    fn non_http_tracker_is_skipped() {
+        assert!(scrape_url("udp://tracker.example.com:80/announce", &HASH).is_none());
+    }
+
+    #[test]
+    fn non_announce_path_is_skipped() {
+        assert!(scrape_url("http://tracker.example.com/foo", &HASH).is_none());
+    }
+}
@@ -1,5 +1,6 @@
 //! Types returned by the API, all supporting serde deserialisation.
 use crate::deser::{empty_as_none, parse_timestamp, u16_from_str, u64_from_str, unit_array};
+use crate::InfoHash;
 use serde::Deserialize;
 
 /// Full details on a torrent.
@@ -33,7 +34,7 @@ pub struct PartialTorrent {
     #[serde(deserialize_with = "u64_from_str")]
     pub id: u64,
     pub name: String,
-    pub info_hash: String,
+    pub info_hash: InfoHash,
     #[serde(deserialize_with = "u64_from_str")]
     pub leechers: u64,
     #[serde(deserialize_with = "u64_from_str")]